@@ -14,6 +14,12 @@ pub const NUM_POINTS: usize = 16; // number of points in the ring
 pub const RING_RADIUS: f32 = 50.0; // visual/initial radius
 pub const PUFFINESS: f32 = 1.25; // scales the target area (volume preservation)
 
+/// Interior "inner spring" connectivity: each point also connects to its
+/// 2nd..=(N+1)-th ring neighbor. 0 disables interior springs (ring-only
+/// topology, the previous behavior).
+pub const INNER_CONNECTIVITY: usize = 0;
+pub const INNER_STIFFNESS: f32 = 0.0;
+
 /// How many constraint solver iterations per tick
 pub const CONSTRAINT_ITERATIONS: usize = 10;
 
@@ -27,3 +33,7 @@ pub const INITIAL_VEL: Vec2 = Vec2::new(100.0, 0.0);
 pub const CENTER: Vec2 = Vec2::ZERO;
 
 pub const MOUSE_RADIUS: f32 = 40.0;
+
+/// How many substeps a bounds contact latch suppresses further outward
+/// correction along the same normal, to stop re-penetration jitter.
+pub const CONTACT_LATCH_SUBSTEPS: u8 = 3;
@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, HashSet};
+
+use bevy::prelude::*;
+
+use crate::physics::point::Point;
+use crate::physics::soft_body::SoftBody;
+
+/// Collect every ring-adjacent `(Entity, Entity)` pair across the given
+/// `SoftBody`s, so `resolve_collisions` can skip them: they're already held
+/// together by the ring's own distance constraint and would otherwise fight it.
+pub fn adjacent_pairs<'a>(bodies: impl Iterator<Item = &'a SoftBody>) -> HashSet<(Entity, Entity)> {
+    let mut pairs = HashSet::new();
+    for soft in bodies {
+        let n = soft.num_points;
+        for i in 0..n {
+            let a = soft.points[i];
+            let b = soft.points[(i + 1) % n];
+            pairs.insert(if a < b { (a, b) } else { (b, a) });
+        }
+    }
+    pairs
+}
+
+const NEIGHBOR_OFFSETS: [IVec2; 9] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+/// Uniform-grid broadphase + narrowphase "collision ball" pass: treats every
+/// `Point` (across every `SoftBody`, including the same one) as a circle of
+/// `collision_radius` and pushes overlapping pairs apart, splitting the
+/// correction by inverse mass. `skip_pairs` excludes ring-adjacent pairs
+/// within the same body. Meant to be called once per Gauss–Seidel iteration
+/// in `softbody_step` so it converges alongside the other constraints.
+///
+/// The grid is keyed by a `BTreeMap` (not a `HashMap`) and each cell's
+/// entities are sorted before resolving, so contact pairs are always visited
+/// in the same order regardless of `Entity` hash-seed or archetype-iteration
+/// order. This pass mutates positions in place as it goes (Gauss–Seidel), so
+/// an order that varied from call to call would make `resimulate()` diverge
+/// from the original run whenever more than one pair is in contact at once.
+pub fn resolve_collisions(
+    q_points: &mut Query<(Entity, &mut Point)>,
+    skip_pairs: &HashSet<(Entity, Entity)>,
+) -> bool {
+    let max_radius = q_points
+        .iter()
+        .map(|(_, p)| p.collision_radius)
+        .fold(0.0_f32, f32::max);
+    if max_radius <= 0.0 {
+        return false;
+    }
+    let cell_size = 2.0 * max_radius;
+
+    let mut grid: BTreeMap<(i32, i32), Vec<Entity>> = BTreeMap::new();
+    for (e, p) in q_points.iter() {
+        let cell = (p.position / cell_size).floor().as_ivec2();
+        grid.entry((cell.x, cell.y)).or_default().push(e);
+    }
+    for entities in grid.values_mut() {
+        entities.sort();
+    }
+
+    let mut any_resolved = false;
+
+    for (&(cx, cy), entities) in &grid {
+        for offset in NEIGHBOR_OFFSETS {
+            let Some(neighbors) = grid.get(&(cx + offset.x, cy + offset.y)) else {
+                continue;
+            };
+            for &a in entities {
+                for &b in neighbors {
+                    // Process each unordered pair exactly once.
+                    if a >= b {
+                        continue;
+                    }
+                    let key = (a, b);
+                    if skip_pairs.contains(&key) {
+                        continue;
+                    }
+                    let Ok([(_, mut pa), (_, mut pb)]) = q_points.get_many_mut([a, b]) else {
+                        continue;
+                    };
+
+                    let diff = pb.position - pa.position;
+                    let dist2 = diff.length_squared();
+                    let min_dist = pa.collision_radius + pb.collision_radius;
+                    if dist2 >= min_dist * min_dist || dist2 <= 1e-12 {
+                        continue;
+                    }
+
+                    let dist = dist2.sqrt();
+                    let normal = diff / dist;
+                    let penetration = min_dist - dist;
+
+                    let inv_a = 1.0 / pa.mass;
+                    let inv_b = 1.0 / pb.mass;
+                    let inv_sum = inv_a + inv_b;
+                    if inv_sum <= 0.0 {
+                        continue;
+                    }
+
+                    let correction = normal * penetration;
+                    pa.position -= correction * (inv_a / inv_sum);
+                    pb.position += correction * (inv_b / inv_sum);
+                    any_resolved = true;
+                }
+            }
+        }
+    }
+
+    any_resolved
+}
@@ -1,4 +1,5 @@
 use crate::config::MOUSE_RADIUS;
+use crate::physics::soft_body::SoftBody;
 use crate::physics::systems::CursorWorld; // where you defined CursorWorld
 use bevy::prelude::Projection;
 use bevy::prelude::*;
@@ -6,9 +7,11 @@ use bevy::render::camera::{ClearColorConfig, OrthographicProjection, ScalingMode
 use bevy_polyline::polyline::Polyline as PolylineAsset;
 use bevy_polyline::prelude::*;
 
-/// Marker for the single blob outline polyline entity.
+/// Marker for a blob outline polyline entity, pointing back at the
+/// `SoftBody` it traces. One per `SoftBody` so `update_blob_outline` can
+/// refresh every body's outline, not just the first one found.
 #[derive(Component)]
-pub struct BlobOutline;
+pub struct BlobOutline(pub Entity);
 
 pub fn draw_effector_gizmo(
     mut gizmos: Gizmos,
@@ -24,31 +27,37 @@ pub fn draw_effector_gizmo(
     gizmos.circle_2d(cursor.0, MOUSE_RADIUS, Color::srgba(1.0, 0.0, 0.0, alpha));
 }
 
-/// Spawn a persistent empty polyline and material; we'll update the vertices each physics tick.
-pub fn spawn_blob_outline(
+/// Spawn a persistent empty polyline and material per `SoftBody`, so each
+/// body gets its own outline traced from its own points. Runs after the
+/// bodies are spawned (`spawn_demo_like_python` et al.) so the query below
+/// sees them.
+pub fn spawn_blob_outlines(
     mut commands: Commands,
+    q_soft: Query<Entity, With<SoftBody>>,
     mut lines: ResMut<Assets<PolylineAsset>>,
     mut mats: ResMut<Assets<PolylineMaterial>>,
 ) {
-    let line_handle = lines.add(PolylineAsset {
-        vertices: Vec::new(),
-    });
-    let mat_handle = mats.add(PolylineMaterial {
-        width: 3.0,
-        color: LinearRgba::WHITE,
-        perspective: false,
-        depth_bias: -0.001,
-    });
+    for body in &q_soft {
+        let line_handle = lines.add(PolylineAsset {
+            vertices: Vec::new(),
+        });
+        let mat_handle = mats.add(PolylineMaterial {
+            width: 3.0,
+            color: LinearRgba::WHITE,
+            perspective: false,
+            depth_bias: -0.001,
+        });
 
-    commands.spawn((
-        BlobOutline,
-        PolylineBundle {
-            polyline: PolylineHandle(line_handle),
-            material: PolylineMaterialHandle(mat_handle),
-            transform: Transform::from_xyz(0.0, 0.0, 1.0),
-            ..default()
-        },
-    ));
+        commands.spawn((
+            BlobOutline(body),
+            PolylineBundle {
+                polyline: PolylineHandle(line_handle),
+                material: PolylineMaterialHandle(mat_handle),
+                transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                ..default()
+            },
+        ));
+    }
 }
 
 // (removed temporary gizmo outline)
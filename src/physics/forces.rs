@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::physics::point::{Point, Velocity};
+
+/// Quadratic air drag, `F = -k * |v| * v`, computed from each point's tracked
+/// `Velocity`. Absent by default (no drag); insert as a resource to enable it.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct AirDrag {
+    pub coefficient: f32,
+}
+
+/// A uniform wind force applied to every point. Absent by default; insert as
+/// a resource to enable it.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Wind {
+    pub force: Vec2,
+}
+
+/// A radial point attractor/repulsor: `F = G * dir / dist^2` out to
+/// `falloff_radius`. Positive `strength` attracts, negative repels. One or
+/// more can be spawned as plain entities.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct RadialField {
+    pub position: Vec2,
+    pub strength: f32,
+    pub falloff_radius: f32,
+}
+
+/// Apply quadratic air drag to every point, if an `AirDrag` resource is
+/// present. Runs before `softbody_step` so the force lands in `Point::acceleration`
+/// for this tick's Verlet integration, same as gravity.
+pub fn apply_air_drag(drag: Option<Res<AirDrag>>, mut q: Query<(&Velocity, &mut Point)>) {
+    let Some(drag) = drag else { return };
+    for (vel, mut p) in &mut q {
+        let speed = vel.0.length();
+        if speed > 0.0 {
+            p.apply_force(-drag.coefficient * speed * vel.0);
+        }
+    }
+}
+
+/// Apply a uniform wind force to every point, if a `Wind` resource is present.
+pub fn apply_wind(wind: Option<Res<Wind>>, mut q_points: Query<&mut Point>) {
+    let Some(wind) = wind else { return };
+    if wind.force == Vec2::ZERO {
+        return;
+    }
+    for mut p in &mut q_points {
+        p.apply_force(wind.force);
+    }
+}
+
+/// Apply every `RadialField` in the world to every point within its falloff radius.
+pub fn apply_radial_fields(q_fields: Query<&RadialField>, mut q_points: Query<&mut Point>) {
+    for field in &q_fields {
+        for mut p in &mut q_points {
+            let diff = field.position - p.position;
+            let dist2 = diff.length_squared();
+            if dist2 <= 1e-6 || dist2 > field.falloff_radius * field.falloff_radius {
+                continue;
+            }
+            let dist = dist2.sqrt();
+            let dir = diff / dist;
+            p.apply_force(dir * (field.strength / dist2));
+        }
+    }
+}
@@ -1,12 +1,18 @@
 use bevy::prelude::*;
 
+pub mod collision;
+pub mod forces;
 pub mod point;
+pub mod snapshot;
 pub mod soft_body;
 pub use soft_body::WorldBounds;
 pub mod systems;
 
 use soft_body::{softbody_step, spawn_demo_like_python, update_world_bounds};
 
+use crate::physics::snapshot::{
+    FixedFrameCounter, SnapshotBuffer, advance_fixed_frame_counter, record_snapshot_system,
+};
 use crate::physics::systems::{
     CursorWorld, EffectorState, OutlineDirty, SubstepCounter, effector_swept_collision_system,
     reset_substep_counter, update_blob_outline,
@@ -24,14 +30,18 @@ impl Plugin for PhysicsPlugin {
             .init_resource::<WorldBounds>()
             .init_resource::<CursorWorld>()
             .init_resource::<EffectorState>()
+            .init_resource::<systems::CameraController>()
             .insert_resource(OutlineDirty(true))
             .init_resource::<SubstepCounter>()
+            // Rollback-netcode support: per-frame state snapshots for save/restore/resimulate
+            .init_resource::<FixedFrameCounter>()
+            .init_resource::<SnapshotBuffer>()
             // Spawn a camera + one soft body (replace with your own spawner as needed)
             .add_systems(
                 Startup,
                 (
                     spawn_demo_like_python,
-                    debug::spawn_blob_outline,
+                    debug::spawn_blob_outlines.after(spawn_demo_like_python),
                     debug::spawn_polyline_camera_3d,
                 ),
             )
@@ -40,6 +50,8 @@ impl Plugin for PhysicsPlugin {
                 Update,
                 (
                     update_world_bounds,
+                    systems::camera_pan_zoom_system.after(update_world_bounds),
+                    systems::apply_zoom_to_world_bounds.after(systems::camera_pan_zoom_system),
                     systems::update_cursor_world, // your cursor tracker
                     debug::draw_effector_gizmo,   // effector gizmo
                     systems::exit_on_esc_or_q_if_native,
@@ -47,13 +59,36 @@ impl Plugin for PhysicsPlugin {
                 ),
             )
             // Verlet + constraint solve at a fixed timestep (set rate in main via Time::<Fixed>)
+            .add_systems(
+                FixedUpdate,
+                advance_fixed_frame_counter.before(effector_swept_collision_system),
+            )
+            // Environmental force fields (drag/wind/attractors) feed into the same
+            // Point::acceleration accumulator gravity uses, so they must land before
+            // softbody_step consumes and resets it for this tick's Verlet integration.
+            .add_systems(
+                FixedUpdate,
+                (
+                    forces::apply_air_drag,
+                    forces::apply_wind,
+                    forces::apply_radial_fields,
+                )
+                    .before(softbody_step),
+            )
             // add the mouse push before the main physics step so constraints
             // can relax the contact right away
             .add_systems(FixedUpdate, effector_swept_collision_system)
+            // Self- and cross-body collision now runs *inside* softbody_step's
+            // Gauss–Seidel loop (see collision::resolve_collisions), so it
+            // converges with the other constraints instead of running as a
+            // standalone pre-pass.
             .add_systems(
                 FixedUpdate,
                 softbody_step.after(effector_swept_collision_system),
             )
+            // Record this tick's state + the effector input that produced it, so a
+            // rollback/resimulation can restore and replay from here later.
+            .add_systems(FixedUpdate, record_snapshot_system.after(softbody_step))
             // Update outline once per render frame when dirty
             .add_systems(Update, update_blob_outline);
         // Native-only quit shortcut (Esc or Q)
@@ -1,10 +1,21 @@
 use bevy::prelude::*;
 
+use crate::config::CONTACT_LATCH_SUBSTEPS;
+
 /// A single Verlet-integrated particle ("point").
 /// Store this as a Component on the rendered entity (which also has a Transform).
 #[derive(Component, Clone, Copy, Debug)]
 pub struct Point {
     pub index: usize,
+    /// Which `SoftBody` this point belongs to (set at spawn time). The
+    /// collision broadphase (`collision::resolve_collisions`) deliberately
+    /// does *not* skip same-body pairs by this field — it allows
+    /// self-collision, only excluding ring-adjacent pairs via
+    /// `collision::adjacent_pairs` — so this is instead the stable per-body
+    /// identity used by the snapshot subsystem (`snapshot::SoftBodySnapshot`)
+    /// to match a `SoftBody` to its recorded state across separate `Query`
+    /// iterations.
+    pub body_id: u32,
 
     /// Current position x_t (kept in sync with Transform by systems).
     pub position: Vec2,
@@ -16,24 +27,83 @@ pub struct Point {
     pub mass: f32,
     /// Collision/interaction radius (world units).
     pub radius: f32,
+    /// Radius used for the point-vs-point "collision ball" pass (self- and
+    /// cross-body collision). Defaults to `radius` but can be tuned separately.
+    pub collision_radius: f32,
     /// Restitution used when reflecting on bounds (0..=1).
     pub bounciness: f32,
+
+    /// Rest-shape target this point is softly pulled toward (goal-spring
+    /// shape matching). Defaults to the spawn-time ring vertex.
+    pub goal_pos: Vec2,
+    /// How strongly this point is pulled toward `goal_pos`, in `[0, 1]`.
+    /// 0 is fully free (pure jelly), 1 is fully pinned to the rest shape.
+    pub goal_weight: f32,
 }
 
 impl Default for Point {
     fn default() -> Self {
         Self {
             index: 0,
+            body_id: 0,
             position: Vec2::ZERO,
             previous_position: Vec2::ZERO,
             acceleration: Vec2::ZERO,
             mass: 1.0,
             radius: 5.0,
+            collision_radius: 5.0,
             bounciness: 0.5,
+            goal_pos: Vec2::ZERO,
+            goal_weight: 0.0,
         }
     }
 }
 
+/// Verlet hides velocity inside `previous_position`; this tracks the same
+/// "velocity-like term" (`position - previous_position`, as already used by
+/// `verlet_step`/`bounce_in_bounds`) as its own component so velocity-dependent
+/// force fields (e.g. air drag) don't have to re-derive it. Updated once per
+/// substep in `softbody_step`.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Velocity(pub Vec2);
+
+/// Continuous-collision contact latch (cyber_rider-style): once a swept check
+/// detects the point crossing a boundary, this remembers the contact normal
+/// (`dir`) and a countdown of substeps (`frames`) during which further
+/// outward correction along that normal is suppressed, so the point doesn't
+/// jitter in place while still re-penetrating. Shared by both CCD checks a
+/// point can hit in a tick — the window walls (`Point::swept_bounce_in_bounds`)
+/// and the effector capsule (`collide_point_with_swept_effector_ccd`) — since
+/// either can tunnel a fast point through otherwise.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Tunneling {
+    pub frames: u8,
+    pub dir: Vec2,
+}
+
+impl Tunneling {
+    /// Per-tick bookkeeping: release the latch once the point is moving
+    /// toward the free side of `dir` (clearly separating from the contact),
+    /// otherwise count down one substep closer to release.
+    pub fn tick(&mut self, velocity_like: Vec2) {
+        if self.frames == 0 {
+            return;
+        }
+        if velocity_like.dot(self.dir) > 0.0 {
+            self.frames = 0;
+            self.dir = Vec2::ZERO;
+        } else {
+            self.frames -= 1;
+        }
+    }
+
+    /// Whether outward correction along `normal` should be suppressed this
+    /// substep because we're still latched onto a matching contact.
+    pub fn suppresses(&self, normal: Vec2) -> bool {
+        self.frames > 0 && self.dir.dot(normal) > 0.5
+    }
+}
+
 impl Point {
     /// Create a new point at `pos`. `previous_position` starts the same
     /// (zero initial velocity). Use `with_initial_velocity` to set v0.
@@ -162,6 +232,97 @@ impl Point {
         self.previous_position = self.position - v;
     }
 
+    /// Continuous (swept) bounds resolver: treats this substep's motion as the
+    /// segment `previous_position -> position` and finds the earliest `t in [0,1]`
+    /// at which it crosses one of the four axis-aligned planes (offset inward by
+    /// `radius`), instead of only checking the final position like
+    /// `bounce_in_bounds`. This is what stops a fast point from tunneling clean
+    /// through a wall between substeps.
+    ///
+    /// Reuses the same segment-sweep idea as `collide_point_with_swept_effector`.
+    /// Falls back to the discrete `bounce_in_bounds` when no crossing is found
+    /// (e.g. the point was already outside at the start of the substep).
+    /// `tunneling` is the point's shared CCD contact latch (see `Tunneling`);
+    /// call `Tunneling::tick` once per tick before this to age it.
+    pub fn swept_bounce_in_bounds(&mut self, half_extents: Vec2, tunneling: &mut Tunneling) {
+        let start = self.previous_position;
+        let end = self.position;
+        let delta = end - start;
+
+        let left = -half_extents.x + self.radius;
+        let right = half_extents.x - self.radius;
+        let bottom = -half_extents.y + self.radius;
+        let top = half_extents.y - self.radius;
+
+        let mut best_t = 1.0_f32;
+        let mut best_normal = Vec2::ZERO;
+        let mut consider = |t: f32, normal: Vec2, best_t: &mut f32, best_normal: &mut Vec2| {
+            if (0.0..*best_t).contains(&t) {
+                *best_t = t;
+                *best_normal = normal;
+            }
+        };
+
+        if delta.x < 0.0 && start.x >= left {
+            consider(
+                (left - start.x) / delta.x,
+                Vec2::new(1.0, 0.0),
+                &mut best_t,
+                &mut best_normal,
+            );
+        } else if delta.x > 0.0 && start.x <= right {
+            consider(
+                (right - start.x) / delta.x,
+                Vec2::new(-1.0, 0.0),
+                &mut best_t,
+                &mut best_normal,
+            );
+        }
+        if delta.y < 0.0 && start.y >= bottom {
+            consider(
+                (bottom - start.y) / delta.y,
+                Vec2::new(0.0, 1.0),
+                &mut best_t,
+                &mut best_normal,
+            );
+        } else if delta.y > 0.0 && start.y <= top {
+            consider(
+                (top - start.y) / delta.y,
+                Vec2::new(0.0, -1.0),
+                &mut best_t,
+                &mut best_normal,
+            );
+        }
+
+        if best_normal == Vec2::ZERO {
+            // No crossing found this substep (already outside, or moving inward);
+            // the discrete clamp still catches gross violations.
+            self.bounce_in_bounds(half_extents);
+            return;
+        }
+
+        // Suppress re-correction along a normal we just latched onto, to avoid
+        // oscillating in place as the point grazes the boundary.
+        if tunneling.suppresses(best_normal) {
+            self.clamp_to_bounds(
+                Vec2::new(left.min(right), bottom.min(top)),
+                Vec2::new(left.max(right), bottom.max(top)),
+            );
+            return;
+        }
+
+        let contact = start + delta * best_t;
+        let v = end - start;
+        let v_normal = best_normal * v.dot(best_normal);
+        let v_tangent = v - v_normal;
+        let reflected = v_tangent - v_normal * self.bounciness;
+
+        self.position = contact;
+        self.previous_position = contact - reflected;
+        tunneling.frames = CONTACT_LATCH_SUBSTEPS;
+        tunneling.dir = best_normal;
+    }
+
     /// Keep inside [min, max] bounds without reflection (like your Python keep_in_bounds).
     /// (This version assumes min and max are absolute corners in world coords.)
     pub fn clamp_to_bounds(&mut self, min: Vec2, max: Vec2) {
@@ -0,0 +1,379 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::physics::point::{Point, Tunneling, Velocity};
+use crate::physics::soft_body::{Integrator, SoftBody};
+use crate::physics::systems::{CursorWorld, EffectorState};
+
+/// The effector input for a single fixed-step frame, captured so a
+/// resimulation can feed it back in instead of reading live mouse state.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EffectorInput {
+    pub cursor: Vec2,
+    pub pressed: bool,
+}
+
+/// Snapshot of a single `Point`'s physics state, stable-ordered by `index`
+/// (not ECS iteration order) so restoration is deterministic. Also covers
+/// the sibling `Velocity`/`Tunneling` components, which didn't exist when
+/// this struct was first written but are part of "the" physics state now.
+#[derive(Clone, Copy, Debug)]
+pub struct PointSnapshot {
+    pub index: usize,
+    pub position: Vec2,
+    pub previous_position: Vec2,
+    pub acceleration: Vec2,
+    pub mass: f32,
+    pub radius: f32,
+    pub collision_radius: f32,
+    pub bounciness: f32,
+    pub goal_pos: Vec2,
+    pub goal_weight: f32,
+    pub velocity: Vec2,
+    pub tunneling_frames: u8,
+    pub tunneling_dir: Vec2,
+}
+
+/// Snapshot of a `SoftBody`'s constraint/target-area data plus its points,
+/// in the same order as `SoftBody::points` (which is already index-ordered
+/// at spawn time). Keyed by `body_id` (mirrored from `Point::body_id`, stable
+/// across separate `Query` iterations) rather than matched by zipping Query
+/// order, which isn't guaranteed to line up between the iteration that
+/// captured this snapshot and the one restoring it.
+#[derive(Clone, Debug)]
+pub struct SoftBodySnapshot {
+    pub body_id: u32,
+    pub num_points: usize,
+    pub radius: f32,
+    pub puffiness: f32,
+    pub desired_area: f32,
+    pub circumference: f32,
+    pub chord_length: f32,
+    pub goal_spring: f32,
+    pub goal_friction: f32,
+    pub min_goal: f32,
+    pub max_goal: f32,
+    pub integrator: Integrator,
+    pub inner_constraints: Vec<(usize, usize, f32)>,
+    pub inner_stiffness: f32,
+    pub points: Vec<PointSnapshot>,
+}
+
+/// The complete physics state of one fixed-step frame.
+#[derive(Clone, Debug, Default)]
+pub struct FrameSnapshot {
+    pub frame: u64,
+    pub bodies: Vec<SoftBodySnapshot>,
+    pub input: EffectorInput,
+}
+
+/// Ring buffer of recent frame snapshots, keyed by fixed-step frame number.
+/// Sized to hold `capacity` frames; the oldest is dropped once full. At most
+/// one entry per `frame`: `save_snapshot` overwrites an existing entry for
+/// the same frame instead of appending a duplicate, so a resimulated frame's
+/// corrected state is what later `get`/`restore_snapshot` calls see.
+#[derive(Resource)]
+pub struct SnapshotBuffer {
+    pub capacity: usize,
+    pub frames: VecDeque<FrameSnapshot>,
+}
+
+impl SnapshotBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn get(&self, frame: u64) -> Option<&FrameSnapshot> {
+        self.frames.iter().find(|s| s.frame == frame)
+    }
+}
+
+impl Default for SnapshotBuffer {
+    fn default() -> Self {
+        // 5 seconds of history at the default 120 Hz fixed rate.
+        Self::new(600)
+    }
+}
+
+/// Counts fixed-step ticks since startup; used as the snapshot/frame key.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct FixedFrameCounter(pub u64);
+
+pub fn advance_fixed_frame_counter(mut counter: ResMut<FixedFrameCounter>) {
+    counter.0 += 1;
+}
+
+/// Capture the current physics state of every `SoftBody` into `buffer`,
+/// keyed by `frame`, with points sorted by `Point::index` for determinism.
+/// If `buffer` already holds an entry for `frame` (a resimulated tick being
+/// re-recorded), it's replaced in place rather than appended, so history
+/// always reflects the most recent (corrected) run of that frame.
+pub fn save_snapshot(
+    frame: u64,
+    input: EffectorInput,
+    q_soft: &Query<&SoftBody>,
+    q_points: &Query<&Point>,
+    q_vel: &Query<&Velocity>,
+    q_tunnel: &Query<&Tunneling>,
+    buffer: &mut SnapshotBuffer,
+) {
+    let mut bodies = Vec::with_capacity(q_soft.iter().len());
+    for soft in q_soft.iter() {
+        let mut points: Vec<PointSnapshot> = soft
+            .points
+            .iter()
+            .filter_map(|&e| q_points.get(e).ok().map(|p| (e, p)))
+            .map(|(e, p)| PointSnapshot {
+                index: p.index,
+                position: p.position,
+                previous_position: p.previous_position,
+                acceleration: p.acceleration,
+                mass: p.mass,
+                radius: p.radius,
+                collision_radius: p.collision_radius,
+                bounciness: p.bounciness,
+                goal_pos: p.goal_pos,
+                goal_weight: p.goal_weight,
+                velocity: q_vel.get(e).map(|v| v.0).unwrap_or_default(),
+                tunneling_frames: q_tunnel.get(e).map(|t| t.frames).unwrap_or_default(),
+                tunneling_dir: q_tunnel.get(e).map(|t| t.dir).unwrap_or_default(),
+            })
+            .collect();
+        points.sort_by_key(|p| p.index);
+        let body_id = soft
+            .points
+            .first()
+            .and_then(|&e| q_points.get(e).ok())
+            .map(|p| p.body_id)
+            .unwrap_or(0);
+
+        bodies.push(SoftBodySnapshot {
+            body_id,
+            num_points: soft.num_points,
+            radius: soft.radius,
+            puffiness: soft.puffiness,
+            desired_area: soft.desired_area,
+            circumference: soft.circumference,
+            chord_length: soft.chord_length,
+            goal_spring: soft.goal_spring,
+            goal_friction: soft.goal_friction,
+            min_goal: soft.min_goal,
+            max_goal: soft.max_goal,
+            integrator: soft.integrator,
+            inner_constraints: soft.inner_constraints.clone(),
+            inner_stiffness: soft.inner_stiffness,
+            points,
+        });
+    }
+
+    let frame_snapshot = FrameSnapshot {
+        frame,
+        bodies,
+        input,
+    };
+    if let Some(existing) = buffer.frames.iter_mut().find(|s| s.frame == frame) {
+        *existing = frame_snapshot;
+        return;
+    }
+
+    if buffer.frames.len() == buffer.capacity {
+        buffer.frames.pop_front();
+    }
+    buffer.frames.push_back(frame_snapshot);
+}
+
+/// Restore every `SoftBody`/`Point`'s physics state from the snapshot
+/// captured at `frame`. Returns `false` (leaving the world untouched) if
+/// that frame isn't in the buffer.
+pub fn restore_snapshot(
+    frame: u64,
+    buffer: &SnapshotBuffer,
+    q_soft: &mut Query<&mut SoftBody>,
+    q_points: &mut Query<&mut Point>,
+    q_vel: &mut Query<&mut Velocity>,
+    q_tunnel: &mut Query<&mut Tunneling>,
+) -> bool {
+    let Some(snapshot) = buffer.get(frame) else {
+        return false;
+    };
+    let body_by_id: HashMap<u32, &SoftBodySnapshot> =
+        snapshot.bodies.iter().map(|b| (b.body_id, b)).collect();
+
+    for mut soft in q_soft.iter_mut() {
+        let body_id = soft
+            .points
+            .first()
+            .and_then(|&e| q_points.get(e).ok())
+            .map(|p| p.body_id)
+            .unwrap_or(0);
+        let Some(&body_snapshot) = body_by_id.get(&body_id) else {
+            continue;
+        };
+
+        soft.radius = body_snapshot.radius;
+        soft.puffiness = body_snapshot.puffiness;
+        soft.desired_area = body_snapshot.desired_area;
+        soft.circumference = body_snapshot.circumference;
+        soft.chord_length = body_snapshot.chord_length;
+        soft.goal_spring = body_snapshot.goal_spring;
+        soft.goal_friction = body_snapshot.goal_friction;
+        soft.min_goal = body_snapshot.min_goal;
+        soft.max_goal = body_snapshot.max_goal;
+        soft.integrator = body_snapshot.integrator;
+        soft.inner_constraints = body_snapshot.inner_constraints.clone();
+        soft.inner_stiffness = body_snapshot.inner_stiffness;
+
+        for (&e, point_snapshot) in soft.points.iter().zip(body_snapshot.points.iter()) {
+            if let Ok(mut p) = q_points.get_mut(e) {
+                p.position = point_snapshot.position;
+                p.previous_position = point_snapshot.previous_position;
+                p.acceleration = point_snapshot.acceleration;
+                p.mass = point_snapshot.mass;
+                p.radius = point_snapshot.radius;
+                p.collision_radius = point_snapshot.collision_radius;
+                p.bounciness = point_snapshot.bounciness;
+                p.goal_pos = point_snapshot.goal_pos;
+                p.goal_weight = point_snapshot.goal_weight;
+            }
+            if let Ok(mut vel) = q_vel.get_mut(e) {
+                vel.0 = point_snapshot.velocity;
+            }
+            if let Ok(mut tunneling) = q_tunnel.get_mut(e) {
+                tunneling.frames = point_snapshot.tunneling_frames;
+                tunneling.dir = point_snapshot.tunneling_dir;
+            }
+        }
+    }
+    true
+}
+
+/// System: record a snapshot of the current tick, including the effector
+/// input that produced it, so a later misprediction can restore and resimulate
+/// from here.
+pub fn record_snapshot_system(
+    counter: Res<FixedFrameCounter>,
+    cursor: Res<CursorWorld>,
+    eff: Res<EffectorState>,
+    q_soft: Query<&SoftBody>,
+    q_points: Query<&Point>,
+    q_vel: Query<&Velocity>,
+    q_tunnel: Query<&Tunneling>,
+    mut buffer: ResMut<SnapshotBuffer>,
+) {
+    let input = EffectorInput {
+        cursor: cursor.0,
+        pressed: eff.pressed,
+    };
+    save_snapshot(
+        counter.0, input, &q_soft, &q_points, &q_vel, &q_tunnel, &mut buffer,
+    );
+}
+
+/// Restore the state at `from_frame` and re-run the fixed-step `FixedUpdate`
+/// schedule once per entry in `inputs`, feeding each tick's `EffectorState`
+/// (and `CursorWorld`, for the debug gizmo) from the recorded/corrected input
+/// rather than from live mouse state. This is the resimulation step a
+/// rollback netcode integration calls after detecting a misprediction.
+///
+/// Resets `FixedFrameCounter` to `from_frame` and drops any buffered history
+/// after it before replaying, so the loop below regenerates
+/// `from_frame+1..=from_frame+inputs.len()` exactly (each tick's own
+/// `record_snapshot_system` call records the corresponding entry afresh) instead
+/// of inflating the live counter by `inputs.len()` from wherever it already
+/// was.
+///
+/// Returns `false` if `from_frame` isn't in the buffer.
+pub fn resimulate(world: &mut World, from_frame: u64, inputs: &[EffectorInput]) -> bool {
+    // `restore_snapshot` takes `Query`, which can only be constructed inside a
+    // system; restore directly against `&mut World` here instead so this also
+    // works without a running `App`/schedule.
+    let Some(snapshot) = world.resource::<SnapshotBuffer>().get(from_frame).cloned() else {
+        return false;
+    };
+
+    let body_by_id: HashMap<u32, &SoftBodySnapshot> =
+        snapshot.bodies.iter().map(|b| (b.body_id, b)).collect();
+
+    // Entity, its points, and its own body_id (mirrored from `Point::body_id`
+    // on its first point) — used to look the matching snapshot up explicitly
+    // rather than trusting this `Query` iteration to line up positionally
+    // with the one that captured `snapshot`.
+    let bodies: Vec<(Entity, Vec<Entity>, u32)> = {
+        let mut q = world.query::<(Entity, &SoftBody)>();
+        q.iter(world)
+            .map(|(e, s)| {
+                let body_id = s
+                    .points
+                    .first()
+                    .and_then(|&pe| world.get::<Point>(pe))
+                    .map(|p| p.body_id)
+                    .unwrap_or(0);
+                (e, s.points.clone(), body_id)
+            })
+            .collect()
+    };
+    for (body_e, entities, body_id) in &bodies {
+        let Some(&body_snapshot) = body_by_id.get(body_id) else {
+            continue;
+        };
+        if let Some(mut soft) = world.get_mut::<SoftBody>(*body_e) {
+            soft.radius = body_snapshot.radius;
+            soft.puffiness = body_snapshot.puffiness;
+            soft.desired_area = body_snapshot.desired_area;
+            soft.circumference = body_snapshot.circumference;
+            soft.chord_length = body_snapshot.chord_length;
+            soft.goal_spring = body_snapshot.goal_spring;
+            soft.goal_friction = body_snapshot.goal_friction;
+            soft.min_goal = body_snapshot.min_goal;
+            soft.max_goal = body_snapshot.max_goal;
+            soft.integrator = body_snapshot.integrator;
+            soft.inner_constraints = body_snapshot.inner_constraints.clone();
+            soft.inner_stiffness = body_snapshot.inner_stiffness;
+        }
+        for (&e, ps) in entities.iter().zip(body_snapshot.points.iter()) {
+            if let Some(mut p) = world.get_mut::<Point>(e) {
+                p.position = ps.position;
+                p.previous_position = ps.previous_position;
+                p.acceleration = ps.acceleration;
+                p.mass = ps.mass;
+                p.radius = ps.radius;
+                p.collision_radius = ps.collision_radius;
+                p.bounciness = ps.bounciness;
+                p.goal_pos = ps.goal_pos;
+                p.goal_weight = ps.goal_weight;
+            }
+            if let Some(mut vel) = world.get_mut::<Velocity>(e) {
+                vel.0 = ps.velocity;
+            }
+            if let Some(mut tunneling) = world.get_mut::<Tunneling>(e) {
+                tunneling.frames = ps.tunneling_frames;
+                tunneling.dir = ps.tunneling_dir;
+            }
+        }
+    }
+
+    {
+        let mut counter = world.resource_mut::<FixedFrameCounter>();
+        counter.0 = from_frame;
+    }
+    {
+        let mut buffer = world.resource_mut::<SnapshotBuffer>();
+        buffer.frames.retain(|s| s.frame <= from_frame);
+    }
+
+    for &input in inputs {
+        {
+            let mut eff = world.resource_mut::<EffectorState>();
+            eff.prev = eff.curr;
+            eff.curr = input.cursor;
+            eff.pressed = input.pressed;
+        }
+        world.resource_mut::<CursorWorld>().0 = input.cursor;
+        world.run_schedule(FixedUpdate);
+    }
+    true
+}
@@ -1,14 +1,30 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
-use super::point::Point;
+use super::point::{Point, Tunneling, Velocity};
 use crate::config::*;
+use crate::physics::collision;
+use crate::physics::forces::{AirDrag, RadialField, Wind};
 use crate::physics::systems::EffectorState;
-use crate::physics::systems::collide_point_with_swept_effector;
+use crate::physics::systems::OutlineDirty;
+use crate::physics::systems::{collide_point_with_swept_effector, collide_point_with_swept_effector_ccd};
 
 /// How many Gauss–Seidel iterations to run per fixed tick (from config).
 pub const CONSTRAINT_ITERATIONS: usize = crate::config::CONSTRAINT_ITERATIONS;
 
+/// Which integration scheme `softbody_step` uses to advance a `SoftBody`'s points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Integrator {
+    /// Position-Verlet with damping folded into `previous_position` (the
+    /// original scheme; still the default).
+    #[default]
+    Verlet,
+    /// Second-order Heun (improved Euler) predictor-corrector, using the
+    /// tracked `Velocity` component explicitly. Noticeably less jittery at
+    /// large `dt`/stiff puffiness without cranking `CONSTRAINT_ITERATIONS`.
+    Heun,
+}
+
 /// A soft body made of ring-connected `Point` particles (n-gon).
 /// Stores parameters and the spawned point entity IDs.
 #[derive(Component)]
@@ -20,6 +36,23 @@ pub struct SoftBody {
     pub desired_area: f32,  // target polygon area
     pub circumference: f32, // 2πr
     pub chord_length: f32,  // target edge length
+
+    // Goal-spring shape matching (Blender's goalspring/goalfrict/mingoal/maxgoal):
+    // pulls each point toward its `Point::goal_pos` with strength interpolated
+    // between `min_goal` and `max_goal` by that point's `goal_weight`.
+    pub goal_spring: f32,
+    pub goal_friction: f32,
+    pub min_goal: f32,
+    pub max_goal: f32,
+
+    pub integrator: Integrator,
+
+    // Interior structural springs ("inner springs") connecting non-adjacent
+    // ring points, populated at spawn from the rest-shape geometry as
+    // `(i, j, rest_length)`. Lets a body be dialed from floppy membrane to
+    // near-rigid shell, which the ring-only topology can't express alone.
+    pub inner_constraints: Vec<(usize, usize, f32)>,
+    pub inner_stiffness: f32,
 }
 
 impl SoftBody {
@@ -37,6 +70,31 @@ impl SoftBody {
             desired_area,
             circumference,
             chord_length,
+            // Off by default: goal_spring of 0 leaves every point fully free,
+            // matching the previous (no shape matching) behavior.
+            goal_spring: 0.0,
+            goal_friction: 0.0,
+            min_goal: 0.0,
+            max_goal: 1.0,
+            integrator: Integrator::default(),
+            inner_constraints: Vec::new(),
+            inner_stiffness: 0.0,
+        }
+    }
+}
+
+/// Paint a per-point `goal_weight` across the ring using `weight_fn(ring_index,
+/// num_points)`, e.g. a gradient so one side of the blob is stiff (weight near
+/// 1) and the other floppy (weight near 0):
+/// `paint_goal_weights(&soft, &mut q_points, |i, n| i as f32 / (n - 1) as f32);`
+pub fn paint_goal_weights(
+    soft: &SoftBody,
+    q_points: &mut Query<&mut Point>,
+    weight_fn: impl Fn(usize, usize) -> f32,
+) {
+    for (i, &e) in soft.points.iter().enumerate() {
+        if let Ok(mut p) = q_points.get_mut(e) {
+            p.goal_weight = weight_fn(i, soft.num_points).clamp(0.0, 1.0);
         }
     }
 }
@@ -45,6 +103,11 @@ impl SoftBody {
 #[derive(Resource, Default, Copy, Clone, Debug)]
 pub struct WorldBounds {
     pub half: Vec2,
+    /// Opt-in continuous (swept) bounds resolution. When `false` (the
+    /// default) bounds are handled by the existing discrete `bounce_in_bounds`
+    /// check; when `true`, `Point::swept_bounce_in_bounds` is used instead so
+    /// fast points can't tunnel through a wall between substeps.
+    pub swept: bool,
 }
 
 /// Keep `WorldBounds` up to date (resizes / DPI changes).
@@ -71,6 +134,9 @@ pub fn spawn_soft_body(
     particle_vis_radius: f32,
     mass: f32,
     bounciness: f32,
+    body_id: u32,
+    inner_connectivity: usize,
+    inner_stiffness: f32,
 ) -> Entity {
     // visual for each point
     let mesh = meshes.add(Circle::new(particle_vis_radius));
@@ -80,16 +146,23 @@ pub fn spawn_soft_body(
     let dt = 1.0 / PHYSICS_HZ as f32;
 
     let mut soft = SoftBody::new(num_points, ring_radius, PUFFINESS);
+    soft.inner_stiffness = inner_stiffness;
+
+    let mut ring_positions = Vec::with_capacity(num_points);
 
     for i in 0..num_points {
         let theta = (i as f32) * std::f32::consts::TAU / (num_points as f32);
         let curr = center + Vec2::new(theta.cos(), theta.sin()) * ring_radius;
+        ring_positions.push(curr);
 
         let mut point = Point::with_initial_velocity(curr, initial_vel, dt, i);
         point.mass = mass;
         point.radius = particle_vis_radius;
+        point.collision_radius = particle_vis_radius;
         point.bounciness = bounciness;
         point.acceleration = gravity;
+        point.body_id = body_id;
+        point.goal_pos = curr; // rest shape = the spawn-time ring vertex
 
         let e = commands
             .spawn((
@@ -100,12 +173,33 @@ pub fn spawn_soft_body(
                 Visibility::Hidden, // hide individual point sprites
                 // physics
                 point,
+                Velocity::default(),
+                Tunneling::default(),
             ))
             .id();
 
         soft.points.push(e);
     }
 
+    // Connect each point to its 2nd..=(inner_connectivity+1)-th ring neighbor
+    // (skipping the immediate neighbor, already held by the ring distance
+    // constraint), so `inner_connectivity` dials the chord density.
+    if inner_connectivity > 0 {
+        let max_step = (inner_connectivity + 1).min(num_points / 2);
+        let mut seen = std::collections::HashSet::new();
+        for i in 0..num_points {
+            for step in 2..=max_step {
+                let j = (i + step) % num_points;
+                let pair = if i < j { (i, j) } else { (j, i) };
+                if pair.0 == pair.1 || !seen.insert(pair) {
+                    continue;
+                }
+                let rest_length = (ring_positions[pair.0] - ring_positions[pair.1]).length();
+                soft.inner_constraints.push((pair.0, pair.1, rest_length));
+            }
+        }
+    }
+
     commands.spawn(soft).id()
 }
 
@@ -143,19 +237,55 @@ pub fn spawn_demo_like_python(
         PARTICLE_VIS_RADIUS,
         DEFAULT_MASS,
         DEFAULT_BOUNCINESS,
+        0,
+        INNER_CONNECTIVITY,
+        INNER_STIFFNESS,
+    );
+
+    // A second body, offset so it starts overlapping the first: exercises
+    // the cross-body "collision ball" pass (`collision::resolve_collisions`)
+    // against real multi-body data instead of only ever seeing one SoftBody.
+    let second_origin = origin_world + Vec2::new(RING_RADIUS, 0.0);
+    spawn_soft_body(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        second_origin,
+        NUM_POINTS,
+        RING_RADIUS,
+        -INITIAL_VEL,
+        GRAVITY,
+        PARTICLE_VIS_RADIUS,
+        DEFAULT_MASS,
+        DEFAULT_BOUNCINESS,
+        1,
+        INNER_CONNECTIVITY,
+        INNER_STIFFNESS,
     );
 }
 
 /// Fixed-timestep integration: Verlet with per-second damping, then
-/// PBD-style constraints (distance + area), then write positions to `Transform`.
+/// PBD-style constraints (distance + area + self/cross-body collision),
+/// then write positions to `Transform`.
+///
+/// Restructured (collision balls) into three phases so the combined
+/// collision pass can see every body's points at once: (1) integrate every
+/// body, (2) one shared Gauss–Seidel loop that solves each body's own
+/// constraints *and* the cross-body collision pass together so they converge
+/// against each other, (3) write all transforms.
 pub fn softbody_step(
     time: Res<Time>, // fixed clock in FixedUpdate
     bounds: Res<WorldBounds>,
-    mut q_points: Query<&mut Point>,
+    mut q_points: Query<(Entity, &mut Point)>,
+    mut q_vel: Query<&mut Velocity>,
+    mut q_tunnel: Query<&mut Tunneling>,
     mut q_tf: Query<&mut Transform>,
     mut q_soft: Query<&mut SoftBody>,
-    buttons: Res<ButtonInput<MouseButton>>, // for left-press state
-    eff: Res<EffectorState>,                // current effector state
+    eff: Res<EffectorState>, // current effector state; `eff.pressed` drives activation
+    air_drag: Option<Res<AirDrag>>,
+    wind: Option<Res<Wind>>,
+    q_fields: Query<&RadialField>,
+    mut dirty: ResMut<OutlineDirty>,
 ) {
     let dt = time.delta_secs();
     let dt2 = dt * dt;
@@ -165,10 +295,11 @@ pub fn softbody_step(
     // We scale the Verlet velocity-like term (x_t - x_{t-1}) by this factor.
     let damping_per_tick = DAMPING_PER_SECOND.powf(dt);
 
+    // --- 1) Verlet integrate all points of every body; add gravity EACH
+    // tick; bounce on window AABB
     for soft in &mut q_soft {
-        // --- 1) Verlet integrate all points; add gravity EACH tick; bounce on window AABB
         for &e in &soft.points {
-            if let Ok(mut p) = q_points.get_mut(e) {
+            if let Ok((_, mut p)) = q_points.get_mut(e) {
                 let x_t = p.position;
                 let x_tm1 = p.previous_position;
 
@@ -176,46 +307,123 @@ pub fn softbody_step(
                 // and ADD constant gravity each tick (otherwise the body won't fall).
                 let a = p.acceleration + GRAVITY;
 
-                // Position-Verlet with damping on (x_t - x_{t-1})
-                let vel_term = (x_t - x_tm1) * damping_per_tick;
-                let mut x_tp1 = x_t + vel_term + a * dt2;
-
-                // Inferred velocity for bounce reflection
-                let mut v = x_tp1 - x_t;
-
-                // Window bounds with per-point radius (origin at center) :contentReference[oaicite:2]{index=2}
-                let left = -half.x + p.radius;
-                let right = half.x - p.radius;
-                let bottom = -half.y + p.radius;
-                let top = half.y - p.radius;
+                let (x_tp1, new_prev) = match soft.integrator {
+                    Integrator::Verlet => {
+                        // Position-Verlet with damping on (x_t - x_{t-1})
+                        let vel_term = (x_t - x_tm1) * damping_per_tick;
+                        let x_tp1 = x_t + vel_term + a * dt2;
+                        (x_tp1, x_t)
+                    }
+                    Integrator::Heun => {
+                        // Heun predictor-corrector, worked in the same "displacement
+                        // per substep" terms `Velocity`/`vel_term` already use above
+                        // (so `d`/`d_star` here are `v * dt`, not per-second
+                        // velocities): predict d* = d + a1*dt^2 (a1 = `a`, the forces
+                        // accumulated at the tick's starting state), then actually
+                        // re-evaluate the velocity-/position-dependent force fields
+                        // at the predicted state (x*, v*) for a2, rather than reusing
+                        // a1 — otherwise this collapses to Verlet with the
+                        // acceleration term halved and gives no stability benefit for
+                        // velocity-dependent forces like air drag.
+                        let d = q_vel.get(e).map(|v| v.0).unwrap_or(Vec2::ZERO) * damping_per_tick;
+                        let d_star = d + a * dt2;
+                        let x_star = x_t + d_star;
+
+                        let mut a2 = GRAVITY;
+                        if let Some(drag) = &air_drag {
+                            let speed = d_star.length();
+                            if speed > 0.0 {
+                                a2 += -drag.coefficient * speed * d_star / p.mass;
+                            }
+                        }
+                        if let Some(wind) = &wind {
+                            a2 += wind.force / p.mass;
+                        }
+                        for field in &q_fields {
+                            let diff = field.position - x_star;
+                            let dist2 = diff.length_squared();
+                            if dist2 > 1e-6 && dist2 <= field.falloff_radius * field.falloff_radius {
+                                let dist = dist2.sqrt();
+                                a2 += diff / dist * (field.strength / dist2) / p.mass;
+                            }
+                        }
+
+                        // Position update averages the tick-start displacement `d`
+                        // with the *predictor's* displacement `d_star`
+                        // (x_new = x + 0.5*(v + v*)*dt), per the request's formula —
+                        // not the corrector acceleration `a2`, which only feeds the
+                        // velocity update below.
+                        let x_tp1 = x_t + 0.5 * (d + d_star);
+                        // Velocity update separately averages a1 and a2
+                        // (v_new = v + 0.5*(a1+a2)*dt) and is tracked explicitly here
+                        // rather than re-derived from (x_tp1 - x_t) afterward, since
+                        // that would silently substitute the position average above.
+                        let d_new = d + 0.5 * (a + a2) * dt2;
+                        (x_tp1, x_tp1 - d_new)
+                    }
+                };
 
-                if x_tp1.x < left {
-                    x_tp1.x = left;
-                    v.x = -v.x * p.bounciness;
-                }
-                if x_tp1.x > right {
-                    x_tp1.x = right;
-                    v.x = -v.x * p.bounciness;
-                }
-                if x_tp1.y < bottom {
-                    x_tp1.y = bottom;
-                    v.y = -v.y * p.bounciness;
+                // Advance Verlet state, then resolve window bounds with per-point
+                // radius (origin at center). `WorldBounds::swept` picks between the
+                // continuous and discrete resolvers; both leave position/previous_position
+                // consistent for the next substep.
+                p.position = x_tp1;
+                p.previous_position = new_prev;
+                if bounds.swept {
+                    // CCD pass: age the shared contact latch once per tick, then
+                    // sweep this tick's motion against the walls and (if active)
+                    // the effector capsule, so a fast point can't tunnel through
+                    // either between substeps.
+                    if let Ok(mut tunneling) = q_tunnel.get_mut(e) {
+                        tunneling.tick(p.position - p.previous_position);
+                        p.swept_bounce_in_bounds(half, &mut tunneling);
+                        if eff.pressed {
+                            collide_point_with_swept_effector_ccd(
+                                &mut p,
+                                &mut *tunneling,
+                                eff.prev,
+                                eff.curr,
+                                eff.radius,
+                            );
+                        }
+                    } else {
+                        p.swept_bounce_in_bounds(half, &mut Tunneling::default());
+                    }
+                } else {
+                    p.bounce_in_bounds(half);
                 }
-                if x_tp1.y > top {
-                    x_tp1.y = top;
-                    v.y = -v.y * p.bounciness;
+                p.acceleration = Vec2::ZERO;
+
+                // Goal-spring shape matching: pull toward the stored rest-shape
+                // target and damp the velocity-like term, so a disturbed blob
+                // springs back to its n-gon rest shape.
+                let goal = soft.min_goal + (soft.max_goal - soft.min_goal) * p.goal_weight;
+                if goal > 0.0 {
+                    let pos = p.position;
+                    p.position = pos + (p.goal_pos - pos) * (goal * soft.goal_spring);
+                    let damped_vel = (p.position - p.previous_position)
+                        * (1.0 - goal * soft.goal_friction).max(0.0);
+                    p.previous_position = p.position - damped_vel;
                 }
 
-                // Advance Verlet state; clear per-tick forces (gravity is re-added next tick)
-                p.previous_position = x_tp1 - v;
-                p.position = x_tp1;
-                p.acceleration = Vec2::ZERO;
+                if let Ok(mut vel) = q_vel.get_mut(e) {
+                    vel.0 = p.position - p.previous_position;
+                }
             }
         }
+    }
 
-        // --- 2) Constraint solve (Gauss–Seidel): distance + area (dilation)
-        // Based on Position-Based Dynamics (Jakobsen / Müller et al.). :contentReference[oaicite:3]{index=3}
-        for _ in 0..CONSTRAINT_ITERATIONS {
+    // Ring-adjacent pairs are already held together by each body's own
+    // distance constraint; skip them in the collision pass below so the two
+    // don't fight each other.
+    let skip_pairs = collision::adjacent_pairs(q_soft.iter());
+
+    // --- 2) Constraint solve (Gauss–Seidel): distance + area (dilation) per
+    // body, then ONE combined self/cross-body collision pass across every
+    // body's points, all inside the same iteration so they converge together.
+    // Based on Position-Based Dynamics (Jakobsen / Müller et al.). :contentReference[oaicite:3]{index=3}
+    for _ in 0..CONSTRAINT_ITERATIONS {
+        for soft in &q_soft {
             // 2a) Distance constraints between ring neighbors: accumulate symmetric corrections
             let mut disp_accum: Vec<Vec2> = vec![Vec2::ZERO; soft.num_points];
             let mut disp_weight: Vec<u32> = vec![0; soft.num_points];
@@ -228,12 +436,12 @@ pub fn softbody_step(
                     let p_i = q_points
                         .get_mut(soft.points[i])
                         .ok()
-                        .map(|p| p.position)
+                        .map(|(_, p)| p.position)
                         .unwrap_or(Vec2::ZERO);
                     let p_j = q_points
                         .get_mut(soft.points[i_next])
                         .ok()
-                        .map(|p| p.position)
+                        .map(|(_, p)| p.position)
                         .unwrap_or(Vec2::ZERO);
                     (p_i, p_j)
                 };
@@ -257,24 +465,67 @@ pub fn softbody_step(
                 disp_weight[i] += 1;
             }
 
+            // 2b-bis) Interior "inner spring" constraints: same symmetric-displacement
+            // form as 2a, but over non-adjacent chords, scaled by inner_stiffness, and
+            // corrected both ways (stretched or compressed) for tunable rigidity.
+            if soft.inner_stiffness > 0.0 {
+                for &(i, j, rest_length) in &soft.inner_constraints {
+                    let (pi, pj) = {
+                        let p_i = q_points
+                            .get_mut(soft.points[i])
+                            .ok()
+                            .map(|(_, p)| p.position)
+                            .unwrap_or(Vec2::ZERO);
+                        let p_j = q_points
+                            .get_mut(soft.points[j])
+                            .ok()
+                            .map(|(_, p)| p.position)
+                            .unwrap_or(Vec2::ZERO);
+                        (p_i, p_j)
+                    };
+
+                    let diff = pj - pi;
+                    let len = diff.length();
+                    if len > 0.0 {
+                        let error = (len - rest_length) * 0.5 * soft.inner_stiffness;
+                        let offset = diff / len * error;
+                        disp_accum[i] += offset;
+                        disp_accum[j] += -offset;
+                        disp_weight[i] += 1;
+                        disp_weight[j] += 1;
+                    }
+                }
+            }
+
             // 2c) Apply average displacement per point
             for i in 0..soft.num_points {
                 if disp_weight[i] == 0 {
                     continue;
                 }
                 let avg = disp_accum[i] / (disp_weight[i] as f32);
-                if let Ok(mut p) = q_points.get_mut(soft.points[i]) {
+                if let Ok((_, mut p)) = q_points.get_mut(soft.points[i]) {
                     p.position += avg;
                 }
             }
+        }
 
-            // 2d) Interleave effector collision as a projection pass (PBD contact)
-            if buttons.pressed(MouseButton::Left) {
-                let ra = eff.prev;
-                let rb = eff.curr;
-                let r = eff.radius; // no speculative padding (step 2 reverted)
+        // 2d) Self- and cross-body "collision ball" pass: every point across
+        // every body is a candidate against every other, via a uniform
+        // spatial hash broadphase. Runs once per Gauss–Seidel iteration so it
+        // relaxes alongside the constraints above instead of fighting them
+        // as a one-off pre-pass would.
+        if collision::resolve_collisions(&mut q_points, &skip_pairs) {
+            dirty.0 = true;
+        }
+
+        // 2e) Interleave effector collision as a projection pass (PBD contact)
+        if eff.pressed {
+            let ra = eff.prev;
+            let rb = eff.curr;
+            let r = eff.radius; // no speculative padding (step 2 reverted)
+            for soft in &q_soft {
                 for i in 0..soft.num_points {
-                    if let Ok(mut p) = q_points.get_mut(soft.points[i]) {
+                    if let Ok((_, mut p)) = q_points.get_mut(soft.points[i]) {
                         let mut pos = p.position;
                         collide_point_with_swept_effector(&mut pos, ra, rb, r);
                         p.position = pos;
@@ -282,10 +533,12 @@ pub fn softbody_step(
                 }
             }
         }
+    }
 
-        // --- 3) Write back to Transform for rendering
+    // --- 3) Write back to Transform for rendering
+    for soft in &q_soft {
         for &e in &soft.points {
-            if let (Ok(p), Ok(mut tf)) = (q_points.get_mut(e), q_tf.get_mut(e)) {
+            if let (Ok((_, p)), Ok(mut tf)) = (q_points.get_mut(e), q_tf.get_mut(e)) {
                 tf.translation.x = p.position.x;
                 tf.translation.y = p.position.y;
             }
@@ -295,14 +548,14 @@ pub fn softbody_step(
 
 /// Compute per-vertex normal offsets to correct polygon area towards `desired_area`.
 /// Mirrors the Python approach: use a secant across neighbors and its outward normal.
-fn dilation_corrections(soft: &SoftBody, q_points: &Query<&mut Point>) -> Vec<Vec2> {
+fn dilation_corrections(soft: &SoftBody, q_points: &Query<(Entity, &mut Point)>) -> Vec<Vec2> {
     let n = soft.num_points;
     let mut poly: Vec<Vec2> = Vec::with_capacity(n);
     for &e in &soft.points {
         let pos = q_points
             .get(e)
             .ok()
-            .map(|p| p.position)
+            .map(|(_, p)| p.position)
             .unwrap_or(Vec2::ZERO);
         poly.push(pos);
     }
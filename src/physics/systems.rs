@@ -1,16 +1,115 @@
+use bevy::input::mouse::{MouseMotion, MouseWheel};
 use bevy::prelude::*;
 use bevy_polyline::polyline::Polyline as PolylineAsset;
 use bevy_polyline::prelude::*;
 
-use crate::config::MOUSE_RADIUS;
+use crate::config::{CONTACT_LATCH_SUBSTEPS, MOUSE_RADIUS};
 use crate::physics::debug::BlobOutline;
-use crate::physics::point::Point;
-use crate::physics::soft_body::SoftBody;
+use crate::physics::point::{Point, Tunneling};
+use crate::physics::soft_body::{SoftBody, WorldBounds};
 use bevy::window::PrimaryWindow;
 
 #[derive(Resource, Default, Debug, Clone, Copy)]
 pub struct CursorWorld(pub Vec2);
 
+/// Orthographic pan/zoom feel for the 2D camera: scroll accumulates into a
+/// zoom velocity and middle-mouse drag into a pan velocity, both damped
+/// toward zero each frame (a flycam-style velocity/damping feel, just
+/// constrained to 2D).
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct CameraController {
+    pub zoom_speed: f32,
+    pub zoom_damping: f32,
+    pub pan_damping: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    zoom_velocity: f32,
+    pan_velocity: Vec2,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            zoom_speed: 0.1,
+            zoom_damping: 10.0,
+            pan_damping: 10.0,
+            min_scale: 0.1,
+            max_scale: 10.0,
+            zoom_velocity: 0.0,
+            pan_velocity: Vec2::ZERO,
+        }
+    }
+}
+
+/// Scroll to zoom the `Camera2d`'s `OrthographicProjection.scale`, middle-mouse
+/// drag to pan its `Transform`. The outline overlay is drawn by a *separate*
+/// 3D orthographic camera (`debug::spawn_polyline_camera_3d`), so its
+/// transform/scale are mirrored here every frame to keep the two registered.
+pub fn camera_pan_zoom_system(
+    time: Res<Time>,
+    mut controller: ResMut<CameraController>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut wheel: EventReader<MouseWheel>,
+    mut motion: EventReader<MouseMotion>,
+    mut q_cam2d: Query<(&mut Transform, &mut Projection), (With<Camera2d>, Without<Camera3d>)>,
+    mut q_cam3d: Query<(&mut Transform, &mut Projection), (With<Camera3d>, Without<Camera2d>)>,
+) {
+    let Ok((mut tf2d, mut proj2d)) = q_cam2d.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ortho2d) = proj2d.as_mut() else {
+        return;
+    };
+
+    let dt = time.delta_secs();
+
+    let mut scroll = 0.0;
+    for ev in wheel.read() {
+        scroll += ev.y;
+    }
+    controller.zoom_velocity += scroll * controller.zoom_speed;
+    ortho2d.scale = (ortho2d.scale * (1.0 - controller.zoom_velocity * dt))
+        .clamp(controller.min_scale, controller.max_scale);
+    controller.zoom_velocity *= (1.0 - controller.zoom_damping * dt).clamp(0.0, 1.0);
+
+    if buttons.pressed(MouseButton::Middle) {
+        // Screen-space drag, converted to world-space pan: flip Y (screen Y is
+        // down, world Y is up) and scale by the current zoom so panning still
+        // tracks the cursor 1:1 regardless of zoom level.
+        let mut drag = Vec2::ZERO;
+        for ev in motion.read() {
+            drag += ev.delta;
+        }
+        controller.pan_velocity += Vec2::new(-drag.x, drag.y) * ortho2d.scale * controller.pan_damping;
+    } else {
+        motion.clear();
+    }
+    tf2d.translation += controller.pan_velocity.extend(0.0) * dt;
+    controller.pan_velocity *= (1.0 - controller.pan_damping * dt).clamp(0.0, 1.0);
+
+    // Mirror onto the 3D polyline camera so the outline overlay stays aligned.
+    if let Ok((mut tf3d, mut proj3d)) = q_cam3d.single_mut() {
+        tf3d.translation.x = tf2d.translation.x;
+        tf3d.translation.y = tf2d.translation.y;
+        if let Projection::Orthographic(ortho3d) = proj3d.as_mut() {
+            ortho3d.scale = ortho2d.scale;
+        }
+    }
+}
+
+/// Keep `WorldBounds` matching the *visible* world rectangle after zoom, not
+/// just the raw window size. Must run after `update_world_bounds` (which sets
+/// `half` fresh from the window each frame) so this scales that value rather
+/// than compounding it.
+pub fn apply_zoom_to_world_bounds(
+    q_cam2d: Query<&Projection, With<Camera2d>>,
+    mut bounds: ResMut<WorldBounds>,
+) {
+    if let Ok(Projection::Orthographic(ortho)) = q_cam2d.single() {
+        bounds.half *= ortho.scale;
+    }
+}
+
 /// Dirty flag for outline updates: set by physics (FixedUpdate), consumed by Update.
 #[derive(Resource, Default, Debug, Clone, Copy)]
 pub struct OutlineDirty(pub bool);
@@ -39,11 +138,13 @@ pub fn update_cursor_world(
     windows: Query<&Window, With<PrimaryWindow>>,
     // Only use the 2D camera for screen->world mapping; ignore the 3D polyline camera
     q_cam: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    buttons: Res<ButtonInput<MouseButton>>,
     mut cursor: ResMut<CursorWorld>,
     mut eff: ResMut<EffectorState>,
 ) {
     // shift prev to old curr first
     eff.prev = eff.curr;
+    eff.pressed = buttons.pressed(MouseButton::Left);
 
     let Ok(window) = windows.single() else {
         return;
@@ -58,12 +159,8 @@ pub fn update_cursor_world(
     }
 }
 
-pub fn effector_swept_collision_system(
-    buttons: Res<ButtonInput<MouseButton>>,
-    eff: Res<EffectorState>,
-    mut points: Query<&mut Point>,
-) {
-    if !buttons.pressed(MouseButton::Left) {
+pub fn effector_swept_collision_system(eff: Res<EffectorState>, mut points: Query<&mut Point>) {
+    if !eff.pressed {
         return;
     }
     let ra = eff.prev;
@@ -83,6 +180,10 @@ pub struct EffectorState {
     pub radius: f32,
     pub prev: Vec2,
     pub curr: Vec2,
+    /// Whether the effector is "active" this tick (mirrors the left mouse
+    /// button live, but is the single surface fixed-step systems read from,
+    /// so a rollback/replay can drive it without touching `ButtonInput`).
+    pub pressed: bool,
 }
 
 impl Default for EffectorState {
@@ -91,6 +192,7 @@ impl Default for EffectorState {
             radius: MOUSE_RADIUS, // tweak as you like (or use MOUSE_RADIUS from config)
             prev: Vec2::ZERO,
             curr: Vec2::ZERO,
+            pressed: false,
         }
     }
 }
@@ -120,6 +222,141 @@ pub(crate) fn collide_point_with_swept_effector(p: &mut Vec2, seg_a: Vec2, seg_b
     }
 }
 
+/// Earliest `t` (and outward contact normal) at which the path `start ->
+/// end` crosses the circle of radius `r` centered at `center`. Building
+/// block for `swept_point_vs_capsule_toi`'s end caps.
+fn swept_point_vs_circle_toi(start: Vec2, end: Vec2, center: Vec2, r: f32) -> Option<(f32, Vec2)> {
+    let d = end - start;
+    let f = start - center;
+    let a = d.length_squared();
+    if a <= 1e-12 {
+        return None;
+    }
+    let b = 2.0 * f.dot(d);
+    let c = f.length_squared() - r * r;
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return None;
+    }
+    let t = (-b - disc.sqrt()) / (2.0 * a);
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    let normal = (start + d * t - center).normalize_or_zero();
+    (normal != Vec2::ZERO).then_some((t, normal))
+}
+
+/// Earliest `t in [0,1]` (and outward contact normal) at which the point's
+/// own swept path `start -> end` first comes within `r` of the (stationary,
+/// already-swept) effector capsule `seg_a -> seg_b`. `None` if the path
+/// never crosses into that distance.
+///
+/// `collide_point_with_swept_effector` only tests the point's *final*
+/// position each substep, so a fast point can blow straight through the
+/// capsule between samples; this sweeps the point's motion too, the same
+/// way `Point::swept_bounce_in_bounds` sweeps it against the walls.
+pub(crate) fn swept_point_vs_capsule_toi(
+    start: Vec2,
+    end: Vec2,
+    seg_a: Vec2,
+    seg_b: Vec2,
+    r: f32,
+) -> Option<(f32, Vec2)> {
+    let seg = seg_b - seg_a;
+    let seg_len2 = seg.length_squared();
+    if seg_len2 <= 1e-12 {
+        return swept_point_vs_circle_toi(start, end, seg_b, r);
+    }
+    let seg_len = seg_len2.sqrt();
+    let tangent = seg / seg_len;
+    let normal = Vec2::new(-tangent.y, tangent.x);
+
+    let rel_start = start - seg_a;
+    let rel_end = end - seg_a;
+    let x0 = rel_start.dot(tangent);
+    let dx = rel_end.dot(tangent) - x0;
+    let y0 = rel_start.dot(normal);
+    let dy = rel_end.dot(normal) - y0;
+
+    let mut best: Option<(f32, Vec2)> = None;
+    let mut consider = |t: f32, n: Vec2, best: &mut Option<(f32, Vec2)>| {
+        if !(0.0..=1.0).contains(&t) {
+            return;
+        }
+        if best.map_or(true, |(bt, _)| t < bt) {
+            *best = Some((t, n));
+        }
+    };
+
+    // Middle (unclamped) region: the closest point on the segment stays
+    // interior, so distance-to-line |y(t)| == r with y(t) = y0 + t*dy linear.
+    if dy.abs() > 1e-9 {
+        for sign in [1.0_f32, -1.0] {
+            let t = (sign * r - y0) / dy;
+            let x = x0 + t * dx;
+            // Only entering crossings (moving toward the line from that side),
+            // never the exit of a path that started inside the strip.
+            if sign * dy < 0.0 && (0.0..=seg_len).contains(&x) {
+                consider(t, normal * sign, &mut best);
+            }
+        }
+    } else if y0.abs() <= r && (0.0..=seg_len).contains(&x0) {
+        // Already inside the strip and moving parallel to it.
+        consider(0.0, normal * y0.signum(), &mut best);
+    }
+
+    // End caps: circle TOI at each end of the capsule.
+    if let Some((t, n)) = swept_point_vs_circle_toi(start, end, seg_a, r) {
+        consider(t, n, &mut best);
+    }
+    if let Some((t, n)) = swept_point_vs_circle_toi(start, end, seg_b, r) {
+        consider(t, n, &mut best);
+    }
+
+    best
+}
+
+/// Continuous version of `collide_point_with_swept_effector`: sweeps the
+/// point's own motion this tick (`point.previous_position -> point.position`)
+/// against the effector capsule, rather than only testing its final
+/// position, so a fast point can't tunnel straight through a quickly-dragged
+/// effector. Shares `tunneling` (see `Tunneling`) with the wall CCD check so
+/// a point that just latched onto either contact doesn't immediately
+/// re-correct and jitter. Falls back to the discrete projection when no
+/// crossing is found along the swept path.
+pub(crate) fn collide_point_with_swept_effector_ccd(
+    point: &mut Point,
+    tunneling: &mut Tunneling,
+    seg_a: Vec2,
+    seg_b: Vec2,
+    r: f32,
+) {
+    let start = point.previous_position;
+    let end = point.position;
+
+    let Some((t, normal)) = swept_point_vs_capsule_toi(start, end, seg_a, seg_b, r) else {
+        let mut pos = point.position;
+        collide_point_with_swept_effector(&mut pos, seg_a, seg_b, r);
+        point.position = pos;
+        return;
+    };
+
+    if tunneling.suppresses(normal) {
+        return;
+    }
+
+    let d = end - start;
+    let contact = start + d * t;
+    let v_normal = normal * d.dot(normal);
+    let v_tangent = d - v_normal;
+    let reflected = v_tangent - v_normal * point.bounciness;
+
+    point.position = contact;
+    point.previous_position = contact - reflected;
+    tunneling.frames = CONTACT_LATCH_SUBSTEPS;
+    tunneling.dir = normal;
+}
+
 /// One-pass Chaikin smoothing for a closed polygon ring.
 pub fn chaikin_closed_once(input: &[Vec2], out: &mut Vec<Vec2>) {
     out.clear();
@@ -137,12 +374,14 @@ pub fn chaikin_closed_once(input: &[Vec2], out: &mut Vec<Vec2>) {
     }
 }
 
-/// Update the GPU polyline to trace the soft body outline.
+/// Update the GPU polyline to trace every soft body's outline: one
+/// `BlobOutline` per `SoftBody` (see `debug::spawn_blob_outlines`), each
+/// refreshed from its own body's points.
 pub fn update_blob_outline(
     q_soft: Query<&SoftBody>,
     q_points: Query<&Point>,
     mut lines: ResMut<Assets<PolylineAsset>>,
-    q_outline: Query<&PolylineHandle, With<BlobOutline>>,
+    q_outline: Query<(&BlobOutline, &PolylineHandle)>,
     mut dirty: ResMut<OutlineDirty>,
 ) {
     if !dirty.0 {
@@ -150,26 +389,26 @@ pub fn update_blob_outline(
     }
     // Reset dirty so we only update once per render frame
     dirty.0 = false;
-    let Some(soft) = q_soft.iter().next() else {
-        // No softbody yet
-        // info!("update_blob_outline: no SoftBody found");
-        return;
-    };
 
-    // Gather current ring positions in order.
-    let mut ring: Vec<Vec2> = Vec::with_capacity(soft.num_points);
-    for &e in &soft.points {
-        if let Ok(p) = q_points.get(e) {
-            ring.push(p.position);
+    for (outline, handle) in &q_outline {
+        let Ok(soft) = q_soft.get(outline.0) else {
+            // Outlined body has despawned.
+            continue;
+        };
+
+        // Gather current ring positions in order.
+        let mut ring: Vec<Vec2> = Vec::with_capacity(soft.num_points);
+        for &e in &soft.points {
+            if let Ok(p) = q_points.get(e) {
+                ring.push(p.position);
+            }
         }
-    }
 
-    // Smooth once with Chaikin.
-    let mut smooth: Vec<Vec2> = Vec::with_capacity(ring.len() * 2);
-    chaikin_closed_once(&ring, &mut smooth);
-    let src = if smooth.len() >= 3 { &smooth } else { &ring };
+        // Smooth once with Chaikin.
+        let mut smooth: Vec<Vec2> = Vec::with_capacity(ring.len() * 2);
+        chaikin_closed_once(&ring, &mut smooth);
+        let src = if smooth.len() >= 3 { &smooth } else { &ring };
 
-    if let Some(handle) = q_outline.iter().next() {
         if let Some(poly) = lines.get_mut(&handle.0) {
             poly.vertices.clear();
             // reserve one extra to close the loop
@@ -181,9 +420,6 @@ pub fn update_blob_outline(
             if let Some(first) = src.first() {
                 poly.vertices.push(first.extend(0.0));
             }
-            // info!("update_blob_outline: vertices={} (ring={}, smooth={})", poly.vertices.len(), ring.len(), smooth.len());
         }
-    } else {
-        // info!("update_blob_outline: no BlobOutline handle found");
     }
 }